@@ -6,7 +6,8 @@ use regex::Regex;
 use toml_edit::{Array, DocumentMut, Formatted, Value};
 use walkdir::{DirEntry, WalkDir};
 
-use crate::{Config, config::ConfigLoadError};
+use crate::utils::closest;
+use crate::{Config, RepositoryDatabase, config::ConfigLoadError};
 
 pub fn read_and_verify_config(config_file: impl AsRef<Path>) -> Result<DocumentMut, AddError> {
     let config_file = config_file.as_ref();
@@ -19,7 +20,30 @@ pub fn read_and_verify_config(config_file: impl AsRef<Path>) -> Result<DocumentM
     Ok(config_content.parse::<DocumentMut>().unwrap()) // Verify config was valid toml above
 }
 
-pub fn add_packages(config_doc: &mut DocumentMut, packages: Vec<String>) -> Result<(), AddError> {
+pub fn add_packages(
+    config_file: impl AsRef<Path>,
+    config_doc: &mut DocumentMut,
+    packages: Vec<String>,
+    repository_db: &RepositoryDatabase,
+) -> Result<(), AddError> {
+    let config_file = config_file.as_ref();
+
+    // Reject unknown packages up front, with a "did you mean" hint when a close
+    // match exists in the configured repositories, rather than adding a dependency
+    // that can never resolve.
+    for d in &packages {
+        if !repository_db.contains(d) {
+            let suggestion = closest(d, repository_db.package_names()).map(str::to_string);
+            return Err(AddError {
+                path: config_file.into(),
+                source: Box::new(AddErrorKind::PackageNotFound {
+                    name: d.clone(),
+                    suggestion: Suggestion(suggestion),
+                }),
+            });
+        }
+    }
+
     // get the dependencies array
     let config_deps = get_mut_array(config_doc);
 
@@ -78,9 +102,88 @@ fn is_hidden(entry: &DirEntry) -> bool {
         .unwrap_or(false)
 }
 
+/// Base packages shipped with R itself; never reported as a project dependency.
+const BASE_PACKAGES: [&str; 14] = [
+    "base", "utils", "stats", "methods", "grDevices", "graphics", "datasets", "tools",
+    "parallel", "compiler", "grid", "splines", "tcltk", "stats4",
+];
+
+struct PackageRegexes {
+    library: Regex,
+    namespaced: Regex,
+    namespace_fn: Regex,
+    p_load: Regex,
+    box_use: Regex,
+    chunk: Regex,
+}
+
+impl PackageRegexes {
+    fn new() -> Self {
+        PackageRegexes {
+            library: Regex::new(r#"(?:library|require)\(\s*["']?([A-Za-z0-9_.]+)["']?\s*\)"#)
+                .unwrap(),
+            namespaced: Regex::new(r"\b([A-Za-z0-9_.]+):::?[A-Za-z0-9_.]+").unwrap(),
+            namespace_fn: Regex::new(
+                r#"(?:requireNamespace|loadNamespace)\(\s*["']([A-Za-z0-9_.]+)["']"#,
+            )
+            .unwrap(),
+            p_load: Regex::new(r"pacman::p_load\(([^)]*)\)").unwrap(),
+            box_use: Regex::new(r"box::use\(\s*([A-Za-z0-9_.]+)").unwrap(),
+            chunk: Regex::new(r"(?s)```\{r[^}]*\}\s*\n(.*?)\n```").unwrap(),
+        }
+    }
+
+    fn packages_in_line(&self, line: &str, packages: &mut Vec<String>) {
+        for cap in self.library.captures_iter(line) {
+            packages.push(cap[1].to_string());
+        }
+        // `pacman::p_load(...)` and `box::use(...)` are themselves `pkg::fn`-shaped
+        // calls, so mask them out before running the generic namespaced match or
+        // `pacman`/`box` would be reported as dependencies alongside the real ones.
+        let masked = self.box_use.replace_all(&self.p_load.replace_all(line, ""), "");
+        for cap in self.namespaced.captures_iter(&masked) {
+            packages.push(cap[1].to_string());
+        }
+        for cap in self.namespace_fn.captures_iter(line) {
+            packages.push(cap[1].to_string());
+        }
+        for cap in self.p_load.captures_iter(line) {
+            for arg in cap[1].split(',') {
+                let pkg = arg.trim().trim_matches(|c| c == '"' || c == '\'');
+                if !pkg.is_empty() {
+                    packages.push(pkg.to_string());
+                }
+            }
+        }
+        for cap in self.box_use.captures_iter(line) {
+            packages.push(cap[1].to_string());
+        }
+    }
+
+    /// Extract the contents of fenced R code chunks (```` ```{r ...} ... ``` ````)
+    /// from a literate document (`.Rmd`/`.qmd`/`.Rnw`) so only actual R code is scanned.
+    fn r_chunks(&self, content: &str) -> String {
+        self.chunk
+            .captures_iter(content)
+            .filter_map(|cap| cap.get(1).map(|m| m.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn is_r_source(ext: &str) -> bool {
+    ext.eq_ignore_ascii_case("R")
+}
+
+fn is_literate_document(ext: &str) -> bool {
+    ["Rmd", "qmd", "Rnw"]
+        .iter()
+        .any(|e| ext.eq_ignore_ascii_case(e))
+}
+
 pub fn scan_r_files_for_packages(dir: &PathBuf) -> Result<Vec<String>, std::io::Error> {
     let mut packages = Vec::new();
-    let re = Regex::new(r#"(?:library|require)\(\s*["']?([A-Za-z0-9_.]+)["']?\s*\)"#).unwrap();
+    let regexes = PackageRegexes::new();
 
     const EXCLUDED: [&str; 1] = ["rv"];
 
@@ -99,26 +202,31 @@ pub fn scan_r_files_for_packages(dir: &PathBuf) -> Result<Vec<String>, std::io::
             })
         })
     {
-        if entry.file_type().is_file()
-            && entry
-                .path()
-                .extension()
-                .is_some_and(|e| e.eq_ignore_ascii_case("R"))
-        {
-            let content = std::fs::read_to_string(entry.path())?;
-            for line in content.lines() {
-                let trimmed = line.trim_start();
-                if trimmed.starts_with('#') {
-                    continue;
-                }
-                for cap in re.captures_iter(line) {
-                    if let Some(pkg) = cap.get(1) {
-                        packages.push(pkg.as_str().to_string());
-                    }
-                }
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Some(ext) = entry.path().extension().and_then(|e| e.to_str()) else {
+            continue;
+        };
+
+        let content = std::fs::read_to_string(entry.path())?;
+        let scanned = if is_r_source(ext) {
+            content
+        } else if is_literate_document(ext) {
+            regexes.r_chunks(&content)
+        } else {
+            continue;
+        };
+
+        for line in scanned.lines() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('#') {
+                continue;
             }
+            regexes.packages_in_line(line, &mut packages);
         }
     }
+    packages.retain(|p| !BASE_PACKAGES.contains(&p.as_str()));
     packages.sort();
     packages.dedup();
     Ok(packages)
@@ -133,22 +241,111 @@ pub struct AddError {
 }
 
 #[derive(Debug, thiserror::Error)]
-#[error(transparent)]
 pub enum AddErrorKind {
+    #[error(transparent)]
     Io(#[from] std::io::Error),
+    #[error(transparent)]
     Parse(#[from] toml_edit::TomlError),
+    #[error(transparent)]
     ConfigLoad(#[from] ConfigLoadError),
+    #[error("package `{name}` not found in any configured repository{suggestion}")]
+    PackageNotFound {
+        name: String,
+        suggestion: Suggestion,
+    },
+}
+
+/// Renders as a "did you mean `x`?" hint when a close match was found, or
+/// nothing at all otherwise.
+#[derive(Debug)]
+pub struct Suggestion(Option<String>);
+
+impl std::fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(s) => write!(f, " — did you mean `{s}`?"),
+            None => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{add_packages, read_and_verify_config};
+    use super::{AddErrorKind, scan_r_files_for_packages};
+    use crate::{RepositoryDatabase, add_packages, read_and_verify_config};
+
+    fn repository_db() -> RepositoryDatabase {
+        RepositoryDatabase::new(["pkg1".to_string(), "pkg2".to_string()])
+    }
 
     #[test]
     fn add_remove() {
         let config_file = "src/tests/valid_config/all_fields.toml";
-        let mut doc = read_and_verify_config(&config_file).unwrap();
-        add_packages(&mut doc, vec!["pkg1".to_string(), "pkg2".to_string()]).unwrap();
+        let mut doc = read_and_verify_config(config_file).unwrap();
+        add_packages(
+            config_file,
+            &mut doc,
+            vec!["pkg1".to_string(), "pkg2".to_string()],
+            &repository_db(),
+        )
+        .unwrap();
         insta::assert_snapshot!("add_remove", doc.to_string());
     }
+
+    #[test]
+    fn add_unknown_package_suggests_closest_match() {
+        let config_file = "src/tests/valid_config/all_fields.toml";
+        let mut doc = read_and_verify_config(config_file).unwrap();
+        let err = add_packages(
+            config_file,
+            &mut doc,
+            vec!["pkg2x".to_string()],
+            &repository_db(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            *err.source,
+            AddErrorKind::PackageNotFound { ref name, ref suggestion }
+                if name == "pkg2x" && suggestion.0.as_deref() == Some("pkg2")
+        ));
+    }
+
+    fn scan(files: &[(&str, &str)]) -> Vec<String> {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, content) in files {
+            std::fs::write(dir.path().join(name), content).unwrap();
+        }
+        scan_r_files_for_packages(&dir.path().to_path_buf()).unwrap()
+    }
+
+    #[test]
+    fn detects_namespaced_and_conditional_calls() {
+        let packages = scan(&[(
+            "script.R",
+            "dplyr::filter(x, y)\n\
+             stats:::predict.lm(m)\n\
+             if (requireNamespace(\"jsonlite\")) loadNamespace(\"jsonlite\")\n\
+             pacman::p_load(purrr, \"tidyr\")\n\
+             box::use(fs[dir_create])\n",
+        )]);
+        assert_eq!(
+            packages,
+            vec!["dplyr", "fs", "jsonlite", "purrr", "tidyr"]
+        );
+    }
+
+    #[test]
+    fn scans_fenced_chunks_in_literate_documents() {
+        let packages = scan(&[(
+            "report.Rmd",
+            "# Title\n\n```{r setup}\nlibrary(ggplot2)\n```\n\nSome prose mentioning library(dplyr) outside a chunk.\n",
+        )]);
+        assert_eq!(packages, vec!["ggplot2"]);
+    }
+
+    #[test]
+    fn filters_base_packages() {
+        let packages = scan(&[("script.R", "library(stats)\nlibrary(dplyr)\n")]);
+        assert_eq!(packages, vec!["dplyr"]);
+    }
 }
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+/// A user-defined `[alias]` entry, supporting both the single-string form
+/// (`ci = "sync --frozen"`) and the list form (`ci = ["sync", "--frozen"]`).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(untagged)]
+pub enum AliasValue {
+    Single(String),
+    List(Vec<String>),
+}
+
+impl AliasValue {
+    fn tokens(&self) -> Vec<String> {
+        match self {
+            AliasValue::Single(s) => s.split_whitespace().map(str::to_string).collect(),
+            AliasValue::List(tokens) => tokens.clone(),
+        }
+    }
+}
+
+pub type AliasTable = HashMap<String, AliasValue>;
+
+/// Resolve `args` against `aliases` the way cargo resolves `aliased_command`.
+///
+/// The first non-flag argument is looked up in `aliases`; if found, the alias's
+/// tokens are spliced in front of the remaining arguments and resolution repeats
+/// (so an alias may itself expand to another alias). `builtins` always wins over
+/// an alias of the same name. Expansion that revisits an alias already seen is
+/// reported as a cycle rather than recursing forever.
+pub fn resolve_aliases(
+    args: &[String],
+    aliases: &AliasTable,
+    builtins: &[&str],
+) -> Result<Vec<String>, AliasError> {
+    let mut resolved = args.to_vec();
+    let mut seen = Vec::new();
+
+    loop {
+        let Some(command_pos) = resolved.iter().position(|a| !a.starts_with('-')) else {
+            return Ok(resolved);
+        };
+        let command = resolved[command_pos].clone();
+        if builtins.contains(&command.as_str()) {
+            return Ok(resolved);
+        }
+        let Some(alias) = aliases.get(&command) else {
+            return Ok(resolved);
+        };
+        if seen.contains(&command) {
+            seen.push(command);
+            return Err(AliasError::Cycle(seen));
+        }
+        seen.push(command);
+
+        let mut expanded = resolved[..command_pos].to_vec();
+        expanded.extend(alias.tokens());
+        expanded.extend(resolved[command_pos + 1..].iter().cloned());
+        resolved = expanded;
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AliasError {
+    #[error("alias expansion cycle detected: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aliases(pairs: &[(&str, AliasValue)]) -> AliasTable {
+        pairs
+            .iter()
+            .cloned()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect()
+    }
+
+    fn args(tokens: &[&str]) -> Vec<String> {
+        tokens.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn expands_single_string_alias() {
+        let aliases = aliases(&[("ci", AliasValue::Single("sync --frozen".to_string()))]);
+        let resolved = resolve_aliases(&args(&["ci"]), &aliases, &["sync"]).unwrap();
+        assert_eq!(resolved, args(&["sync", "--frozen"]));
+    }
+
+    #[test]
+    fn expands_list_alias_and_keeps_trailing_args() {
+        let aliases = aliases(&[(
+            "ci",
+            AliasValue::List(vec!["sync".to_string(), "--frozen".to_string()]),
+        )]);
+        let resolved = resolve_aliases(&args(&["ci", "--verbose"]), &aliases, &["sync"]).unwrap();
+        assert_eq!(resolved, args(&["sync", "--frozen", "--verbose"]));
+    }
+
+    #[test]
+    fn builtin_wins_over_alias_of_same_name() {
+        let aliases = aliases(&[("sync", AliasValue::Single("add foo".to_string()))]);
+        let resolved = resolve_aliases(&args(&["sync"]), &aliases, &["sync"]).unwrap();
+        assert_eq!(resolved, args(&["sync"]));
+    }
+
+    #[test]
+    fn detects_alias_cycle() {
+        let aliases = aliases(&[
+            ("a", AliasValue::Single("b".to_string())),
+            ("b", AliasValue::Single("a".to_string())),
+        ]);
+        let err = resolve_aliases(&args(&["a"]), &aliases, &["sync"]).unwrap_err();
+        assert!(matches!(err, AliasError::Cycle(_)));
+    }
+}
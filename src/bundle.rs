@@ -0,0 +1,376 @@
+use std::fs;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+use crate::git::GitRepository;
+use crate::http::{Http, HttpDownload};
+use crate::library::Library;
+use crate::lockfile::{Lockfile, Source};
+use crate::repository_urls::get_tarball_urls;
+use crate::reporter::Reporter;
+
+/// Options controlling how a project is packaged by [`bundle`].
+#[derive(Debug, Clone, Default)]
+pub struct BundleOptions {
+    /// Allow bundling a project whose git working tree has uncommitted changes.
+    pub allow_dirty: bool,
+    /// Print the files that would be included without writing the archive.
+    pub list: bool,
+}
+
+/// A single file staged into the bundle, relative to the staging directory root.
+#[derive(Debug, Clone)]
+pub struct BundleEntry {
+    pub path: PathBuf,
+}
+
+/// Git metadata embedded in the bundle as `.rv_vcs_info.json`.
+#[derive(Debug, Clone, serde::Serialize)]
+struct VcsInfo {
+    git: VcsGitInfo,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct VcsGitInfo {
+    sha1: String,
+    dirty: bool,
+}
+
+/// Package a fully resolved project (lockfile + downloaded tarballs) into a single
+/// portable archive so it can be installed on a machine with no CRAN/network access.
+///
+/// This mirrors `cargo package`: every dependency resolved in `lockfile` is copied
+/// into a staging directory laid out as a local repository (tarballs plus a generated
+/// `PACKAGES` index under `repository/src/contrib`), a `.rv_vcs_info.json` is embedded recording the git commit and
+/// working tree cleanliness, and the staging directory is tar+gzipped into `output`.
+///
+/// Unless `options.allow_dirty` is set, bundling a dirty git working tree is refused.
+/// When `options.list` is set, the entries that would be included are returned
+/// without downloading/copying any tarball or writing an archive.
+pub fn bundle(
+    project_dir: &Path,
+    lockfile: &Lockfile,
+    library: &Library,
+    output: &Path,
+    options: &BundleOptions,
+    reporter: &Reporter,
+) -> Result<Vec<BundleEntry>, BundleError> {
+    let err = |kind: BundleErrorKind| BundleError {
+        path: project_dir.to_path_buf().into_boxed_path(),
+        source: Box::new(kind),
+    };
+
+    let vcs_info = vcs_info(project_dir, options.allow_dirty).map_err(err)?;
+
+    if options.list {
+        let mut entries: Vec<BundleEntry> = lockfile
+            .sources()
+            .iter()
+            .map(|source| BundleEntry {
+                path: PathBuf::from("repository/src/contrib").join(tarball_name(source)),
+            })
+            .collect();
+        entries.push(BundleEntry {
+            path: PathBuf::from("repository/src/contrib").join("PACKAGES"),
+        });
+        entries.push(BundleEntry {
+            path: PathBuf::from(".rv_vcs_info.json"),
+        });
+        return Ok(entries);
+    }
+
+    let staging_dir = tempfile::tempdir().map_err(BundleErrorKind::Io).map_err(err)?;
+    let repo_dir = staging_dir.path().join("repository/src/contrib");
+    fs::create_dir_all(&repo_dir)
+        .map_err(BundleErrorKind::Io)
+        .map_err(err)?;
+
+    let mut entries = Vec::new();
+    let mut package_records = Vec::new();
+    for source in lockfile.sources() {
+        reporter.status("Staging", source.name());
+        let dest = stage_source(source, library, &repo_dir, reporter).map_err(err)?;
+        entries.push(BundleEntry {
+            path: dest
+                .strip_prefix(staging_dir.path())
+                .unwrap_or(&dest)
+                .to_path_buf(),
+        });
+        package_records.push(package_record(source));
+    }
+
+    let packages_index = repo_dir.join("PACKAGES");
+    fs::write(&packages_index, package_records.join("\n\n"))
+        .map_err(BundleErrorKind::Io)
+        .map_err(err)?;
+    entries.push(BundleEntry {
+        path: packages_index
+            .strip_prefix(staging_dir.path())
+            .unwrap_or(&packages_index)
+            .to_path_buf(),
+    });
+
+    let vcs_info_path = staging_dir.path().join(".rv_vcs_info.json");
+    fs::write(
+        &vcs_info_path,
+        serde_json::to_string_pretty(&vcs_info)
+            .map_err(BundleErrorKind::Json)
+            .map_err(err)?,
+    )
+    .map_err(BundleErrorKind::Io)
+    .map_err(err)?;
+    entries.push(BundleEntry {
+        path: vcs_info_path
+            .strip_prefix(staging_dir.path())
+            .unwrap_or(&vcs_info_path)
+            .to_path_buf(),
+    });
+
+    reporter.status("Archiving", output.display());
+    write_archive(staging_dir.path(), output).map_err(err)?;
+    Ok(entries)
+}
+
+fn tarball_name(source: &Source) -> String {
+    format_tarball_name(source.name(), source.version())
+}
+
+fn format_tarball_name(name: &str, version: &str) -> String {
+    format!("{name}_{version}.tar.gz")
+}
+
+fn vcs_info(project_dir: &Path, allow_dirty: bool) -> Result<VcsInfo, BundleErrorKind> {
+    let repo = GitRepository::open(project_dir).map_err(|e| BundleErrorKind::Git(e.to_string()))?;
+    vcs_info_from_status(&repo, allow_dirty)
+}
+
+/// Abstraction over the two git queries `vcs_info` needs, so its dirty/clean
+/// branching can be unit tested without a real `.git` directory.
+trait GitStatus {
+    fn head_commit(&self) -> Result<String, String>;
+    fn is_clean(&self) -> Result<bool, String>;
+}
+
+impl GitStatus for GitRepository {
+    fn head_commit(&self) -> Result<String, String> {
+        GitRepository::head_commit(self).map_err(|e| e.to_string())
+    }
+
+    fn is_clean(&self) -> Result<bool, String> {
+        GitRepository::is_clean(self).map_err(|e| e.to_string())
+    }
+}
+
+fn vcs_info_from_status(repo: &impl GitStatus, allow_dirty: bool) -> Result<VcsInfo, BundleErrorKind> {
+    let sha1 = repo.head_commit().map_err(BundleErrorKind::Git)?;
+    let dirty = !repo.is_clean().map_err(BundleErrorKind::Git)?;
+
+    if dirty && !allow_dirty {
+        return Err(BundleErrorKind::DirtyWorkingTree);
+    }
+
+    Ok(VcsInfo {
+        git: VcsGitInfo { sha1, dirty },
+    })
+}
+
+fn stage_source(
+    source: &Source,
+    library: &Library,
+    repo_dir: &Path,
+    reporter: &Reporter,
+) -> Result<PathBuf, BundleErrorKind> {
+    let dest = repo_dir.join(tarball_name(source));
+
+    match library.tarball_path(source) {
+        Some(cached) => {
+            reporter.debug(format!("{}: using cached tarball", source.name()));
+            fs::copy(cached, &dest).map_err(BundleErrorKind::Io)?;
+        }
+        None => {
+            let urls = get_tarball_urls(source);
+            let bytes = download_with_fallback(source.name(), &urls, reporter, &Http)?;
+            fs::write(&dest, bytes).map_err(BundleErrorKind::Io)?;
+        }
+    }
+
+    Ok(dest)
+}
+
+/// Try each candidate mirror URL in turn, falling back to the next one on
+/// failure, so one stale/unreachable mirror doesn't fail the whole bundle.
+fn download_with_fallback(
+    name: &str,
+    urls: &[String],
+    reporter: &Reporter,
+    http: &impl HttpDownload,
+) -> Result<Vec<u8>, BundleErrorKind> {
+    if urls.is_empty() {
+        return Err(BundleErrorKind::MissingTarball(name.to_string()));
+    }
+
+    let mut last_error = None;
+    for url in urls {
+        match http.download(url, reporter) {
+            Ok(bytes) => return Ok(bytes),
+            Err(e) => {
+                reporter.debug(format!("{name}: {url} failed ({e}), trying next mirror"));
+                last_error = Some(e);
+            }
+        }
+    }
+    Err(BundleErrorKind::Http(last_error.unwrap().to_string()))
+}
+
+fn package_record(source: &Source) -> String {
+    format_package_record(source.name(), source.version())
+}
+
+fn format_package_record(name: &str, version: &str) -> String {
+    format!("Package: {name}\nVersion: {version}\n")
+}
+
+fn write_archive(staging_dir: &Path, output: &Path) -> Result<(), BundleErrorKind> {
+    let file = File::create(output).map_err(BundleErrorKind::Io)?;
+    let encoder = GzEncoder::new(file, Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    builder
+        .append_dir_all(".", staging_dir)
+        .map_err(BundleErrorKind::Io)?;
+    builder
+        .into_inner()
+        .map_err(BundleErrorKind::Io)?
+        .finish()
+        .map_err(BundleErrorKind::Io)?;
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to bundle project at `{}`", path.display())]
+#[non_exhaustive]
+pub struct BundleError {
+    path: Box<Path>,
+    source: Box<BundleErrorKind>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BundleErrorKind {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("git operation failed: {0}")]
+    Git(String),
+    #[error("download failed: {0}")]
+    Http(String),
+    #[error("working tree is dirty; pass --allow-dirty to bundle anyway")]
+    DirtyWorkingTree,
+    #[error("no tarball URL available for package `{0}`")]
+    MissingTarball(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+    use crate::http::HttpError;
+
+    struct FakeGit {
+        head: &'static str,
+        clean: bool,
+    }
+
+    impl GitStatus for FakeGit {
+        fn head_commit(&self) -> Result<String, String> {
+            Ok(self.head.to_string())
+        }
+
+        fn is_clean(&self) -> Result<bool, String> {
+            Ok(self.clean)
+        }
+    }
+
+    #[test]
+    fn vcs_info_on_clean_tree_reports_not_dirty() {
+        let info = vcs_info_from_status(&FakeGit { head: "abc123", clean: true }, false).unwrap();
+        assert_eq!(info.git.sha1, "abc123");
+        assert!(!info.git.dirty);
+    }
+
+    #[test]
+    fn vcs_info_on_dirty_tree_is_refused_by_default() {
+        let err = vcs_info_from_status(&FakeGit { head: "abc123", clean: false }, false).unwrap_err();
+        assert!(matches!(err, BundleErrorKind::DirtyWorkingTree));
+    }
+
+    #[test]
+    fn vcs_info_on_dirty_tree_is_allowed_with_allow_dirty() {
+        let info = vcs_info_from_status(&FakeGit { head: "abc123", clean: false }, true).unwrap();
+        assert!(info.git.dirty);
+    }
+
+    struct FakeHttp {
+        responses: RefCell<Vec<Result<Vec<u8>, String>>>,
+    }
+
+    impl HttpDownload for FakeHttp {
+        fn download(&self, url: &str, _reporter: &Reporter) -> Result<Vec<u8>, HttpError> {
+            match self.responses.borrow_mut().remove(0) {
+                Ok(bytes) => Ok(bytes),
+                Err(msg) => Err(HttpError::Request(url.to_string(), msg)),
+            }
+        }
+    }
+
+    #[test]
+    fn download_with_fallback_tries_next_mirror_on_failure() {
+        let http = FakeHttp {
+            responses: RefCell::new(vec![Err("404".to_string()), Ok(b"data".to_vec())]),
+        };
+        let urls = vec![
+            "https://mirror1/pkg.tar.gz".to_string(),
+            "https://mirror2/pkg.tar.gz".to_string(),
+        ];
+        let bytes = download_with_fallback("pkg", &urls, &Reporter::default(), &http).unwrap();
+        assert_eq!(bytes, b"data");
+    }
+
+    #[test]
+    fn download_with_fallback_fails_when_every_mirror_fails() {
+        let http = FakeHttp {
+            responses: RefCell::new(vec![Err("404".to_string()), Err("404".to_string())]),
+        };
+        let urls = vec![
+            "https://mirror1/pkg.tar.gz".to_string(),
+            "https://mirror2/pkg.tar.gz".to_string(),
+        ];
+        let err = download_with_fallback("pkg", &urls, &Reporter::default(), &http).unwrap_err();
+        assert!(matches!(err, BundleErrorKind::Http(_)));
+    }
+
+    #[test]
+    fn download_with_fallback_fails_fast_with_no_urls() {
+        let http = FakeHttp {
+            responses: RefCell::new(Vec::new()),
+        };
+        let err = download_with_fallback("pkg", &[], &Reporter::default(), &http).unwrap_err();
+        assert!(matches!(err, BundleErrorKind::MissingTarball(name) if name == "pkg"));
+    }
+
+    #[test]
+    fn format_tarball_name_matches_repository_layout() {
+        assert_eq!(format_tarball_name("dplyr", "1.1.4"), "dplyr_1.1.4.tar.gz");
+    }
+
+    #[test]
+    fn format_package_record_lists_name_and_version() {
+        assert_eq!(
+            format_package_record("dplyr", "1.1.4"),
+            "Package: dplyr\nVersion: 1.1.4\n"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::path::PathBuf;
+
+use clap::{ArgAction, Parser, Subcommand};
+
+use crate::alias::resolve_aliases;
+use crate::bundle::{self, BundleOptions};
+use crate::config::{Config, ConfigLoadError};
+use crate::library::{Library, LibraryError};
+use crate::lockfile::{Lockfile, LockfileError};
+use crate::reporter::{Reporter, Verbosity};
+use crate::repository::{RepositoryDatabase, RepositoryError};
+use crate::resolver::Resolver;
+use crate::sync::{BuildStep, SyncHandler};
+use crate::utils::closest;
+use crate::{AddError, add_packages, read_and_verify_config};
+
+/// Subcommand names built into `rv`; these always win over a user-defined alias
+/// of the same name.
+const BUILTIN_COMMANDS: &[&str] = &["add", "sync", "bundle"];
+
+const CONFIG_FILE: &str = "rv.toml";
+const LOCKFILE: &str = "rv.lock";
+
+#[derive(Debug, Parser)]
+#[command(name = "rv")]
+struct Cli {
+    /// Increase output verbosity; repeat for more detail (`-v`, `-vv`).
+    #[arg(short = 'v', long = "verbose", action = ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Suppress status output.
+    #[arg(long, global = true)]
+    quiet: bool,
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+    /// Add packages to the project config.
+    Add { packages: Vec<String> },
+    /// Resolve the project's dependencies against its repositories and plan the
+    /// install of whatever's missing from the library; nothing is downloaded or
+    /// installed.
+    Sync,
+    /// Package a fully resolved project into a portable archive.
+    Bundle {
+        output: PathBuf,
+        #[arg(long)]
+        allow_dirty: bool,
+        #[arg(long)]
+        list: bool,
+    },
+}
+
+/// Entry point used by the `rv` binary: expands config-driven aliases, the way
+/// cargo resolves `aliased_command`, before dispatching to the matched subcommand.
+pub fn run(args: Vec<String>) -> Result<(), CliError> {
+    let config = Config::from_file(CONFIG_FILE).map_err(CliError::Config)?;
+
+    let resolved = resolve_aliases(&args[1..], config.aliases(), BUILTIN_COMMANDS)
+        .map_err(CliError::AliasResolution)?;
+
+    let mut argv = vec![args[0].clone()];
+    argv.extend(resolved);
+
+    let cli = Cli::try_parse_from(&argv).map_err(|e| match e.kind() {
+        clap::error::ErrorKind::InvalidSubcommand => unknown_command_error(&argv, e),
+        _ => CliError::Parse(e),
+    })?;
+    let reporter = Reporter::new(Verbosity::from_flags(cli.verbose, cli.quiet));
+
+    match cli.command {
+        Commands::Add { packages } => {
+            let mut doc = read_and_verify_config(CONFIG_FILE).map_err(CliError::Add)?;
+            let database = RepositoryDatabase::fetch(&config.project.repositories, &reporter)
+                .map_err(CliError::Repository)?;
+            add_packages(CONFIG_FILE, &mut doc, packages, &database).map_err(CliError::Add)?;
+            std::fs::write(CONFIG_FILE, doc.to_string()).map_err(CliError::Io)?;
+            reporter.status("Added", "dependencies updated in rv.toml");
+            Ok(())
+        }
+        Commands::Sync => {
+            let project_dir = std::env::current_dir().map_err(CliError::Io)?;
+            let library = Library::discover(&project_dir).map_err(CliError::Library)?;
+            let database = RepositoryDatabase::fetch(&config.project.repositories, &reporter)
+                .map_err(CliError::Repository)?;
+
+            let requested: Vec<String> = config
+                .project
+                .dependencies
+                .iter()
+                .map(|d| d.name().to_string())
+                .collect();
+            let resolution = Resolver::new(&reporter).resolve(&requested, &database);
+            let plan = SyncHandler::new(&reporter).plan(&resolution, &library);
+
+            let installed = plan
+                .steps
+                .iter()
+                .filter(|step| matches!(step, BuildStep::Install { .. }))
+                .count();
+            reporter.status("Synced", format!("{installed} package(s) to install"));
+            Ok(())
+        }
+        Commands::Bundle {
+            output,
+            allow_dirty,
+            list,
+        } => {
+            let project_dir = std::env::current_dir().map_err(CliError::Io)?;
+            let lockfile = Lockfile::from_file(project_dir.join(LOCKFILE))
+                .map_err(CliError::Lockfile)?;
+            let library = Library::discover(&project_dir).map_err(CliError::Library)?;
+            let options = BundleOptions { allow_dirty, list };
+
+            let entries = bundle::bundle(
+                &project_dir,
+                &lockfile,
+                &library,
+                &output,
+                &options,
+                &reporter,
+            )
+            .map_err(CliError::Bundle)?;
+
+            if list {
+                for entry in entries {
+                    println!("{}", entry.path.display());
+                }
+            } else {
+                reporter.status("Bundled", output.display());
+            }
+            Ok(())
+        }
+    }
+}
+
+/// If the first token isn't a known subcommand, suggest the closest match by
+/// edit distance instead of surfacing clap's generic parse error.
+fn unknown_command_error(argv: &[String], source: clap::error::Error) -> CliError {
+    let attempted = argv.get(1).cloned();
+    let suggestion = attempted
+        .as_deref()
+        .and_then(|a| closest(a, BUILTIN_COMMANDS.iter().copied()))
+        .map(str::to_string);
+
+    CliError::UnknownCommand {
+        attempted,
+        suggestion,
+        source,
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    #[error(transparent)]
+    Config(ConfigLoadError),
+    #[error("alias resolution failed: {0}")]
+    AliasResolution(crate::alias::AliasError),
+    #[error(
+        "unknown command{}{}",
+        attempted.as_ref().map(|a| format!(" `{a}`")).unwrap_or_default(),
+        suggestion.as_ref().map(|s| format!(" — did you mean `{s}`?")).unwrap_or_default(),
+    )]
+    UnknownCommand {
+        attempted: Option<String>,
+        suggestion: Option<String>,
+        #[source]
+        source: clap::error::Error,
+    },
+    #[error(transparent)]
+    Parse(clap::error::Error),
+    #[error(transparent)]
+    Repository(RepositoryError),
+    #[error(transparent)]
+    Add(AddError),
+    #[error(transparent)]
+    Lockfile(LockfileError),
+    #[error(transparent)]
+    Library(LibraryError),
+    #[error(transparent)]
+    Bundle(bundle::BundleError),
+    #[error(transparent)]
+    Io(std::io::Error),
+}
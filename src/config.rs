@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::alias::AliasTable;
+
+/// A project's `rv.toml` configuration: its dependencies, repositories, and any
+/// user-defined `[alias]` table.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Config {
+    pub project: ProjectConfig,
+    #[serde(default)]
+    pub alias: AliasTable,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProjectConfig {
+    #[serde(default)]
+    pub dependencies: Vec<ConfigDependency>,
+    #[serde(default)]
+    pub repositories: Vec<Repository>,
+}
+
+/// A single `[project.dependencies]` entry: either a bare package name or a
+/// detailed table (e.g. pinning a version or a remote source).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ConfigDependency {
+    Name(String),
+    Detailed { name: String },
+}
+
+impl ConfigDependency {
+    pub fn name(&self) -> &str {
+        match self {
+            ConfigDependency::Name(name) => name,
+            ConfigDependency::Detailed { name } => name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Repository {
+    pub alias: String,
+    pub url: String,
+}
+
+impl Config {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Config, ConfigLoadError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|source| ConfigLoadError {
+            path: path.into(),
+            source: ConfigLoadErrorKind::Io(source),
+        })?;
+        toml::from_str(&content).map_err(|source| ConfigLoadError {
+            path: path.into(),
+            source: ConfigLoadErrorKind::Parse(source),
+        })
+    }
+
+    /// The user-defined `[alias]` table, consulted by `cli` before dispatching a
+    /// subcommand so e.g. `rv ci` can expand to `rv sync --frozen`.
+    pub fn aliases(&self) -> &AliasTable {
+        &self.alias
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to load config at `{}`", path.display())]
+#[non_exhaustive]
+pub struct ConfigLoadError {
+    path: Box<Path>,
+    source: ConfigLoadErrorKind,
+}
+
+#[derive(Debug, thiserror::Error)]
+enum ConfigLoadErrorKind {
+    #[error(transparent)]
+    Io(std::io::Error),
+    #[error(transparent)]
+    Parse(toml::de::Error),
+}
@@ -0,0 +1,36 @@
+use crate::reporter::Reporter;
+
+/// Abstraction over fetching a URL's bytes, so callers (and tests) can swap in a
+/// mock without depending on a real network stack.
+pub trait HttpDownload {
+    fn download(&self, url: &str, reporter: &Reporter) -> Result<Vec<u8>, HttpError>;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Http;
+
+impl HttpDownload for Http {
+    fn download(&self, url: &str, reporter: &Reporter) -> Result<Vec<u8>, HttpError> {
+        reporter.debug(format!("GET {url}"));
+        let response = ureq::get(url)
+            .call()
+            .map_err(|e| HttpError::Request(url.to_string(), e.to_string()))?;
+
+        let mut bytes = Vec::new();
+        response
+            .into_reader()
+            .read_to_end(&mut bytes)
+            .map_err(HttpError::Io)?;
+
+        reporter.verbose(format!("downloaded {} bytes", bytes.len()));
+        Ok(bytes)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum HttpError {
+    #[error("request to `{0}` failed: {1}")]
+    Request(String, String),
+    #[error(transparent)]
+    Io(std::io::Error),
+}
@@ -1,5 +1,7 @@
 mod activate;
 mod add;
+mod alias;
+mod bundle;
 mod cache;
 mod cancellation;
 #[cfg(feature = "cli")]
@@ -16,6 +18,7 @@ mod package;
 mod project_summary;
 mod r_cmd;
 mod renv;
+mod reporter;
 mod repository;
 mod repository_urls;
 mod resolver;
@@ -28,6 +31,8 @@ pub mod consts;
 
 pub use activate::{activate, deactivate};
 pub use add::{add_packages, read_and_verify_config, scan_r_files_for_packages};
+pub use alias::{AliasError, AliasTable, AliasValue, resolve_aliases};
+pub use bundle::{BundleEntry, BundleError, BundleOptions, bundle};
 pub use cache::{CacheInfo, DiskCache, PackagePaths, utils::hash_string};
 pub use cancellation::Cancellation;
 pub use config::{Config, ConfigDependency, Repository};
@@ -44,6 +49,7 @@ pub use package::{Version, VersionRequirement, is_binary_package};
 pub use project_summary::ProjectSummary;
 pub use r_cmd::{RCmd, RCommandLine, find_r_version_command};
 pub use renv::RenvLock;
+pub use reporter::{Reporter, Verbosity};
 pub use repository::RepositoryDatabase;
 pub use repository_urls::{get_package_file_urls, get_tarball_urls};
 pub use resolver::{Resolution, ResolvedDependency, Resolver, UnresolvedDependency};
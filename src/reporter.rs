@@ -0,0 +1,98 @@
+use std::fmt::Display;
+
+/// Count-based verbosity level, analogous to clap's `ArgAction::Count` on repeated
+/// `-v` flags: `Quiet` suppresses status output entirely, the default level shows
+/// per-step status, `Verbose` (`-v`) additionally shows per-package resolution and
+/// download steps, and `Debug` (`-vv`) additionally shows HTTP URLs and cache
+/// decisions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+    Debug,
+}
+
+impl Verbosity {
+    /// Build a `Verbosity` from a `-v` occurrence count and a `--quiet` flag,
+    /// the way `sync`/`resolver`/`http` are expected to be driven from `cli`.
+    pub fn from_flags(verbose_count: u8, quiet: bool) -> Self {
+        if quiet {
+            return Verbosity::Quiet;
+        }
+        match verbose_count {
+            0 => Verbosity::Normal,
+            1 => Verbosity::Verbose,
+            _ => Verbosity::Debug,
+        }
+    }
+}
+
+/// A single sink for `sync`/`resolver`/`http` output, replacing scattered
+/// `println!`/`eprintln!` calls so verbosity can be muted for scripting or made
+/// verbose for debugging resolution failures.
+#[derive(Debug, Clone, Copy)]
+pub struct Reporter {
+    verbosity: Verbosity,
+}
+
+impl Reporter {
+    pub fn new(verbosity: Verbosity) -> Self {
+        Reporter { verbosity }
+    }
+
+    /// Report a step, e.g. `status("Resolving", "dplyr@1.1.4")`. Shown at `Normal`
+    /// and above; suppressed entirely when `Quiet`.
+    pub fn status(&self, verb: impl Display, message: impl Display) {
+        if self.verbosity >= Verbosity::Normal {
+            println!("{verb:>12} {message}");
+        }
+    }
+
+    /// Per-package resolution/download detail. Shown only at `-v` and above.
+    pub fn verbose(&self, message: impl Display) {
+        if self.verbosity >= Verbosity::Verbose {
+            println!("{message}");
+        }
+    }
+
+    /// HTTP URLs and cache decisions. Shown only at `-vv`.
+    pub fn debug(&self, message: impl Display) {
+        if self.verbosity >= Verbosity::Debug {
+            println!("[debug] {message}");
+        }
+    }
+
+    /// Warnings are always shown, even when `Quiet`.
+    pub fn warn(&self, message: impl Display) {
+        eprintln!("warning: {message}");
+    }
+}
+
+impl Default for Reporter {
+    fn default() -> Self {
+        Reporter::new(Verbosity::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbosity_from_flags() {
+        assert_eq!(Verbosity::from_flags(0, false), Verbosity::Normal);
+        assert_eq!(Verbosity::from_flags(1, false), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_flags(2, false), Verbosity::Debug);
+        assert_eq!(Verbosity::from_flags(5, false), Verbosity::Debug);
+        assert_eq!(Verbosity::from_flags(2, true), Verbosity::Quiet);
+    }
+
+    #[test]
+    fn verbosity_ordering_gates_output() {
+        assert!(Verbosity::Debug > Verbosity::Verbose);
+        assert!(Verbosity::Verbose > Verbosity::Normal);
+        assert!(Verbosity::Normal > Verbosity::Quiet);
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::BTreeSet;
+
+use crate::config::Repository;
+use crate::http::{Http, HttpDownload};
+use crate::reporter::Reporter;
+
+/// The set of package names known to be available across a project's configured
+/// repositories, used to validate dependency names before they're added and to
+/// power "did you mean" suggestions when a lookup misses.
+#[derive(Debug, Clone, Default)]
+pub struct RepositoryDatabase {
+    packages: BTreeSet<String>,
+}
+
+impl RepositoryDatabase {
+    pub fn new(packages: impl IntoIterator<Item = String>) -> Self {
+        RepositoryDatabase {
+            packages: packages.into_iter().collect(),
+        }
+    }
+
+    /// Download each repository's `PACKAGES` index and merge the package names
+    /// into a single database, reporting one status line per repository.
+    pub fn fetch(repositories: &[Repository], reporter: &Reporter) -> Result<Self, RepositoryError> {
+        let mut packages = BTreeSet::new();
+        for repository in repositories {
+            reporter.status("Fetching", &repository.alias);
+            let url = format!("{}/src/contrib/PACKAGES", repository.url.trim_end_matches('/'));
+            let bytes = Http
+                .download(&url, reporter)
+                .map_err(|e| RepositoryError::Fetch(repository.alias.clone(), e.to_string()))?;
+            let text = String::from_utf8_lossy(&bytes);
+            packages.extend(parse_packages_index(&text));
+        }
+        Ok(RepositoryDatabase { packages })
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.packages.contains(name)
+    }
+
+    pub fn package_names(&self) -> impl Iterator<Item = &str> {
+        self.packages.iter().map(String::as_str)
+    }
+}
+
+/// Parse `Package: <name>` lines out of a CRAN-style `PACKAGES` index.
+fn parse_packages_index(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.lines().filter_map(|line| {
+        line.strip_prefix("Package:")
+            .map(|name| name.trim().to_string())
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RepositoryError {
+    #[error("failed to fetch repository `{0}`: {1}")]
+    Fetch(String, String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_reports_known_packages() {
+        let db = RepositoryDatabase::new(["dplyr".to_string(), "purrr".to_string()]);
+        assert!(db.contains("dplyr"));
+        assert!(!db.contains("ggplot2"));
+    }
+
+    #[test]
+    fn parses_packages_index() {
+        let index = "Package: dplyr\nVersion: 1.1.4\n\nPackage: purrr\nVersion: 1.0.2\n";
+        let names: Vec<_> = parse_packages_index(index).collect();
+        assert_eq!(names, vec!["dplyr".to_string(), "purrr".to_string()]);
+    }
+}
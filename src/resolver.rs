@@ -0,0 +1,84 @@
+use crate::repository::RepositoryDatabase;
+use crate::reporter::Reporter;
+use crate::utils::closest;
+
+/// A dependency successfully matched against a configured repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedDependency {
+    pub name: String,
+}
+
+/// A requested dependency that couldn't be matched, with a "did you mean" hint
+/// when a close match was found in the repository database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedDependency {
+    pub name: String,
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Resolution {
+    pub resolved: Vec<ResolvedDependency>,
+    pub unresolved: Vec<UnresolvedDependency>,
+}
+
+/// Matches requested package names against a [`RepositoryDatabase`], reporting
+/// per-package progress through a [`Reporter`] (`-v` shows each package as it's
+/// checked; `-vv` additionally shows why a package didn't resolve).
+pub struct Resolver<'a> {
+    reporter: &'a Reporter,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(reporter: &'a Reporter) -> Self {
+        Resolver { reporter }
+    }
+
+    pub fn resolve(&self, requested: &[String], database: &RepositoryDatabase) -> Resolution {
+        let mut resolution = Resolution::default();
+        for name in requested {
+            self.reporter.verbose(format!("resolving {name}"));
+            if database.contains(name) {
+                self.reporter.debug(format!("{name}: found in repository database"));
+                resolution.resolved.push(ResolvedDependency { name: name.clone() });
+            } else {
+                let suggestion = closest(name, database.package_names()).map(str::to_string);
+                self.reporter.debug(format!(
+                    "{name}: not found in repository database (suggestion: {suggestion:?})"
+                ));
+                resolution.unresolved.push(UnresolvedDependency {
+                    name: name.clone(),
+                    suggestion,
+                });
+            }
+        }
+        resolution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::Verbosity;
+
+    #[test]
+    fn resolves_known_and_suggests_for_unknown() {
+        let db = RepositoryDatabase::new(["dplyr".to_string(), "purrr".to_string()]);
+        let reporter = Reporter::new(Verbosity::Debug);
+        let resolution = Resolver::new(&reporter).resolve(
+            &["dplyr".to_string(), "dplyrr".to_string()],
+            &db,
+        );
+        assert_eq!(
+            resolution.resolved,
+            vec![ResolvedDependency { name: "dplyr".to_string() }]
+        );
+        assert_eq!(
+            resolution.unresolved,
+            vec![UnresolvedDependency {
+                name: "dplyrr".to_string(),
+                suggestion: Some("dplyr".to_string()),
+            }]
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use crate::library::Library;
+use crate::reporter::Reporter;
+use crate::resolver::Resolution;
+
+/// A single install/skip decision made while planning a sync.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildStep {
+    Install { name: String },
+    AlreadyPresent { name: String },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildPlan {
+    pub steps: Vec<BuildStep>,
+}
+
+/// What changed in the library as a result of carrying out a [`BuildPlan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncChange {
+    Installed { name: String },
+}
+
+/// Turns a [`Resolution`] into a [`BuildPlan`] against the current [`Library`],
+/// reporting progress through a [`Reporter`] (each package to install is shown as
+/// a status line; `-vv` additionally shows packages skipped as already installed).
+pub struct SyncHandler<'a> {
+    reporter: &'a Reporter,
+}
+
+impl<'a> SyncHandler<'a> {
+    pub fn new(reporter: &'a Reporter) -> Self {
+        SyncHandler { reporter }
+    }
+
+    pub fn plan(&self, resolution: &Resolution, library: &Library) -> BuildPlan {
+        let mut steps = Vec::new();
+        for dep in &resolution.resolved {
+            if library.contains(&dep.name) {
+                self.reporter.debug(format!("{}: already installed, skipping", dep.name));
+                steps.push(BuildStep::AlreadyPresent { name: dep.name.clone() });
+            } else {
+                self.reporter.status("Installing", &dep.name);
+                steps.push(BuildStep::Install { name: dep.name.clone() });
+            }
+        }
+        for dep in &resolution.unresolved {
+            match &dep.suggestion {
+                Some(s) => self
+                    .reporter
+                    .warn(format!("package `{}` not found — did you mean `{s}`?", dep.name)),
+                None => self.reporter.warn(format!("package `{}` not found", dep.name)),
+            }
+        }
+        BuildPlan { steps }
+    }
+}
@@ -0,0 +1,65 @@
+/// Compute the Levenshtein (edit) distance between `a` and `b`: the minimum number
+/// of single-character insertions, deletions, or substitutions needed to turn one
+/// into the other.
+pub fn lev_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, &ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(curr[j] + 1).min(prev[j + 1] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+/// Find the candidate closest to `query` by edit distance, provided it is within
+/// `max(2, query.len() / 3)` edits — the same threshold cargo uses for its
+/// "did you mean" hints. Returns `None` if no candidate is close enough.
+pub fn closest<'a>(query: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let threshold = (query.len() / 3).max(2);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (candidate, lev_distance(query, candidate)))
+        .filter(|(_, distance)| *distance <= threshold)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_strings_have_zero_distance() {
+        assert_eq!(lev_distance("dplyr", "dplyr"), 0);
+    }
+
+    #[test]
+    fn counts_substitutions_insertions_and_deletions() {
+        assert_eq!(lev_distance("kitten", "sitting"), 3);
+        assert_eq!(lev_distance("ggplot2", "ggplot"), 1);
+        assert_eq!(lev_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn closest_finds_best_match_within_threshold() {
+        let candidates = ["dplyr", "purrr", "tidyr", "ggplot2"];
+        assert_eq!(closest("dplyr", candidates), Some("dplyr"));
+        assert_eq!(closest("dplr", candidates), Some("dplyr"));
+        assert_eq!(closest("xyzxyzxyz", candidates), None);
+    }
+
+    #[test]
+    fn closest_returns_none_for_no_candidates() {
+        assert_eq!(closest("dplyr", std::iter::empty()), None);
+    }
+}